@@ -1,6 +1,10 @@
 use anyhow::anyhow;
 use anyhow::Result;
-use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::format::{Locale, StrftimeItems};
+use chrono::{
+    DateTime, Duration as ChronoDuration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
 use log::{debug, info};
 use powerpack::{output, Icon, Item};
 use std::env;
@@ -13,14 +17,77 @@ use std::time::Duration;
 const ICON_DIR: &str = "/System/Library/CoreServices/CoreTypes.bundle/Contents/Resources/";
 const CLOCK_ICON: &str = "icon.png";
 const CALENDAR_ICON: &str = "/System/Applications/Calendar.app";
-const OUTPUT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const DEFAULT_OUTPUT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const OUTPUT_FORMAT_VAR: &str = "OUTPUT_FORMAT";
+const TIMEZONES_VAR: &str = "TIMEZONES";
+const LOCALE_VAR: &str = "LOCALE";
+const DEFAULT_LOCALE: Locale = Locale::POSIX;
+
+fn resolve_locale(raw: Option<&str>) -> Locale {
+    match raw {
+        Some(name) if !name.is_empty() => name.parse::<Locale>().unwrap_or_else(|_| {
+            debug!("Ignoring unrecognised {} '{}'", LOCALE_VAR, name);
+            DEFAULT_LOCALE
+        }),
+        _ => DEFAULT_LOCALE,
+    }
+}
+
+fn configured_locale() -> Locale {
+    resolve_locale(env::var(LOCALE_VAR).ok().as_deref())
+}
+
+fn resolve_timezones(raw: &str) -> Vec<Tz> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| match name.parse::<Tz>() {
+            Ok(tz) => Some(tz),
+            Err(e) => {
+                debug!("Ignoring unrecognised {} entry '{}': {}", TIMEZONES_VAR, name, e);
+                None
+            }
+        })
+        .collect()
+}
+
+fn configured_timezones() -> Vec<Tz> {
+    resolve_timezones(&env::var(TIMEZONES_VAR).unwrap_or_default())
+}
+
+fn resolve_output_format(raw: Option<&str>) -> String {
+    match raw {
+        Some(pattern) if !pattern.is_empty() => pattern.to_string(),
+        _ => DEFAULT_OUTPUT_DATE_FORMAT.to_string(),
+    }
+}
+
+fn output_date_format() -> String {
+    resolve_output_format(env::var(OUTPUT_FORMAT_VAR).ok().as_deref())
+}
+
+fn validate_output_format(pattern: &str) -> Result<()> {
+    if StrftimeItems::new(pattern).any(|item| matches!(item, chrono::format::Item::Error)) {
+        return Err(anyhow!("Invalid {}: '{}'", OUTPUT_FORMAT_VAR, pattern));
+    }
+    Ok(())
+}
+
+fn error_item(subtitle: String) -> Item {
+    Item::new("Error")
+        .subtitle(subtitle)
+        .icon(Icon::with_image(
+            format!("{}/AlertStopIcon.icns", ICON_DIR).as_str(),
+        ))
+}
 
 trait ToAlfredItem {
-    fn to_utc_item(&self, description: &str) -> Item;
-    fn to_localtime_item(&self, description: &str) -> Item;
+    fn to_utc_item(&self, description: &str, format: &str, locale: Locale) -> Item;
+    fn to_localtime_item(&self, description: &str, format: &str, locale: Locale) -> Item;
+    fn to_timezone_item(&self, tz: Tz, description: &str, format: &str, locale: Locale) -> Item;
     fn to_relative_item(&self) -> Item;
     fn to_timestamp_items(&self, description: &str) -> Vec<Item>;
-    fn to_output(&self, source: Input) -> Vec<Item>;
+    fn to_output(&self, source: Input, format: &str, timezones: &[Tz], locale: Locale) -> Vec<Item>;
 }
 
 #[derive(Debug)]
@@ -31,11 +98,12 @@ enum Input {
 }
 
 impl ToAlfredItem for NaiveDateTime {
-    fn to_utc_item(&self, description: &str) -> Item {
+    fn to_utc_item(&self, description: &str, format: &str, locale: Locale) -> Item {
         let utc_dt = DateTime::<Utc>::from_utc(*self, Utc);
         debug!("UTC Datetime: {:?}", utc_dt);
 
-        let formatted_date = utc_dt.format(OUTPUT_DATE_FORMAT);
+        let formatted_date =
+            utc_dt.format_localized_with_items(StrftimeItems::new_with_locale(format, locale), locale);
 
         Item::new(formatted_date.to_string())
             .subtitle(format!("From {}: UTC", description))
@@ -43,7 +111,7 @@ impl ToAlfredItem for NaiveDateTime {
             .arg(formatted_date.to_string())
     }
 
-    fn to_localtime_item(&self, description: &str) -> Item {
+    fn to_localtime_item(&self, description: &str, format: &str, locale: Locale) -> Item {
         let local_dt: DateTime<Local> = DateTime::from(DateTime::<Utc>::from_utc(*self, Utc));
 
         debug!(
@@ -52,7 +120,8 @@ impl ToAlfredItem for NaiveDateTime {
             local_dt.offset().to_string()
         );
 
-        let formatted_date = local_dt.format(OUTPUT_DATE_FORMAT);
+        let formatted_date = local_dt
+            .format_localized_with_items(StrftimeItems::new_with_locale(format, locale), locale);
 
         Item::new(formatted_date.to_string())
             .subtitle(format!(
@@ -64,6 +133,26 @@ impl ToAlfredItem for NaiveDateTime {
             .arg(formatted_date.to_string())
     }
 
+    fn to_timezone_item(&self, tz: Tz, description: &str, format: &str, locale: Locale) -> Item {
+        let tz_dt = Utc.from_utc_datetime(self).with_timezone(&tz);
+
+        debug!("{} datetime: {:?}, offset: {}", tz, tz_dt, tz_dt.offset());
+
+        let formatted_date =
+            tz_dt.format_localized_with_items(StrftimeItems::new_with_locale(format, locale), locale);
+
+        Item::new(formatted_date.to_string())
+            .subtitle(format!(
+                "From {}: {} ({}, {})",
+                description,
+                tz,
+                tz_dt.format("%Z"),
+                tz_dt.offset()
+            ))
+            .icon(Icon::with_file_icon(CALENDAR_ICON))
+            .arg(formatted_date.to_string())
+    }
+
     fn to_relative_item(&self) -> Item {
         let utc_dt = DateTime::<Utc>::from_utc(*self, Utc);
         debug!("UTC: {}", utc_dt.to_rfc3339());
@@ -115,39 +204,82 @@ impl ToAlfredItem for NaiveDateTime {
         ]
     }
 
-    fn to_output(&self, source: Input) -> Vec<Item> {
+    fn to_output(&self, source: Input, format: &str, timezones: &[Tz], locale: Locale) -> Vec<Item> {
         debug!("Creating outputs for input source: {:?}", source);
         match source {
             Clipboard(query) => {
                 let mut items = match query.parse::<i64>() {
-                    Ok(_) => vec![
-                        self.to_localtime_item("timestamp from clipboard"),
-                        self.to_utc_item("timestamp from clipboard"),
-                        self.to_relative_item(),
-                    ],
-                    Err(_) => self.to_timestamp_items("Time since epoch"),
+                    Ok(_) => {
+                        let mut items = vec![
+                            self.to_localtime_item("timestamp from clipboard", format, locale),
+                            self.to_utc_item("timestamp from clipboard", format, locale),
+                        ];
+                        items.extend(timezones.iter().map(|&tz| {
+                            self.to_timezone_item(tz, "timestamp from clipboard", format, locale)
+                        }));
+                        items.push(self.to_relative_item());
+                        items
+                    }
+                    Err(_) => {
+                        let mut items = self.to_timestamp_items("Time since epoch");
+                        items.push(self.to_localtime_item("date from clipboard", format, locale));
+                        items.push(self.to_utc_item("date from clipboard", format, locale));
+                        items.extend(timezones.iter().map(|&tz| {
+                            self.to_timezone_item(tz, "date from clipboard", format, locale)
+                        }));
+                        items
+                    }
                 };
-                items.extend(Utc::now().naive_utc().to_output(Input::None));
+                items.extend(
+                    Utc::now()
+                        .naive_utc()
+                        .to_output(Input::None, format, timezones, locale),
+                );
                 items
             }
             Input::Argument(query) => {
                 let mut items = match query.parse::<i64>() {
-                    Ok(_) => vec![
-                        self.to_localtime_item("timestamp"),
-                        self.to_utc_item("timestamp"),
-                        self.to_relative_item(),
-                    ],
-                    Err(_) => self.to_timestamp_items("Time since epoch"),
+                    Ok(_) => {
+                        let mut items = vec![
+                            self.to_localtime_item("timestamp", format, locale),
+                            self.to_utc_item("timestamp", format, locale),
+                        ];
+                        items.extend(timezones.iter().map(|&tz| {
+                            self.to_timezone_item(tz, "timestamp", format, locale)
+                        }));
+                        items.push(self.to_relative_item());
+                        items
+                    }
+                    Err(_) => {
+                        let mut items = self.to_timestamp_items("Time since epoch");
+                        items.push(self.to_localtime_item("date", format, locale));
+                        items.push(self.to_utc_item("date", format, locale));
+                        items.extend(
+                            timezones
+                                .iter()
+                                .map(|&tz| self.to_timezone_item(tz, "date", format, locale)),
+                        );
+                        items
+                    }
                 };
-                items.extend(Utc::now().naive_utc().to_output(Input::None));
+                items.extend(
+                    Utc::now()
+                        .naive_utc()
+                        .to_output(Input::None, format, timezones, locale),
+                );
                 items
             }
             Input::None => {
                 let mut items = self.to_timestamp_items("Current time");
                 items.extend(vec![
-                    self.to_localtime_item("Current time"),
-                    self.to_utc_item("Current time"),
+                    self.to_localtime_item("Current time", format, locale),
+                    self.to_utc_item("Current time", format, locale),
                 ]);
+                items.extend(
+                    timezones
+                        .iter()
+                        .map(|&tz| self.to_timezone_item(tz, "Current time", format, locale)),
+                );
                 items
             }
         }
@@ -184,10 +316,22 @@ fn run_workflow(
 ) -> Result<Vec<Item>, Box<dyn Error>> {
     let mut items = vec![];
 
+    let format = output_date_format();
+    if let Err(e) = validate_output_format(&format) {
+        debug!("Rejecting configured output format: {}", e);
+        output(iter::once(error_item(format!(
+            "Invalid {} workflow variable: '{}'",
+            OUTPUT_FORMAT_VAR, format
+        ))))?;
+        return Err(Box::from(e));
+    }
+    let timezones = configured_timezones();
+    let locale = configured_locale();
+
     if query.is_empty() {
         if let Some(content) = clipboard_content {
             match parse_datetime(content.as_str()) {
-                Ok(dt) => items.extend(dt.to_output(Clipboard(content))),
+                Ok(dt) => items.extend(dt.to_output(Clipboard(content), &format, &timezones, locale)),
                 Err(e) => {
                     debug!("Couldn't parse clipboard to date: {}", e)
                 }
@@ -198,27 +342,28 @@ fn run_workflow(
     if !query.is_empty() {
         match parse_datetime(&query) {
             Ok(dt) => {
-                items.extend(dt.to_output(Input::Argument(query)));
+                items.extend(dt.to_output(Input::Argument(query), &format, &timezones, locale));
             }
             Err(e) => {
                 debug!(
                     "Failed to parse input '{}', giving up. Final error: {}",
                     query, e
                 );
-                output(iter::once(
-                    Item::new("Error")
-                        .subtitle(format!("Failed to parse '{}' to a date", query))
-                        .icon(Icon::with_image(
-                            format!("{}/AlertStopIcon.icns", ICON_DIR).as_str(),
-                        )),
-                ))?;
+                output(iter::once(error_item(format!(
+                    "Failed to parse '{}' to a date",
+                    query
+                ))))?;
                 return Err(Box::from(e));
             }
         };
     }
 
     if items.is_empty() {
-        items.extend(Utc::now().naive_utc().to_output(Input::None));
+        items.extend(
+            Utc::now()
+                .naive_utc()
+                .to_output(Input::None, &format, &timezones, locale),
+        );
     }
 
     Ok(items)
@@ -234,6 +379,7 @@ fn parse_datetime(s: &str) -> Result<NaiveDateTime> {
         .or(parse_date_and_time(s))
         .or(parse_date(s))
         .or(parse_time(s))
+        .or(parse_relative(s))
 }
 
 fn parse_timestamp(s: &str) -> Result<NaiveDateTime> {
@@ -272,11 +418,32 @@ fn parse_timestamp(s: &str) -> Result<NaiveDateTime> {
 
 fn parse_iso8601(s: &str) -> Result<NaiveDateTime> {
     debug!("Attempting to parse ISO8601 format");
-    Ok(s.parse::<DateTime<Utc>>()?.naive_utc())
+    // Chrono's `DateTime<Utc>` parser already copes with fractional seconds of
+    // any length and both `Z` and `±HH:MM`/`±HHMM` offsets; the one real-world
+    // variant it rejects is a space instead of `T` between date and time, so
+    // normalize just that before handing off.
+    Ok(normalize_iso8601_separator(s).parse::<DateTime<Utc>>()?.naive_utc())
+}
+
+fn normalize_iso8601_separator(s: &str) -> String {
+    if let Some(idx) = s.find(' ') {
+        let (date_part, rest) = s.split_at(idx);
+        let time_part = &rest[1..];
+        if !date_part.contains(':')
+            && date_part.contains('-')
+            && time_part.starts_with(|c: char| c.is_ascii_digit())
+        {
+            return format!("{}T{}", date_part, time_part);
+        }
+    }
+    s.to_string()
 }
 
 fn parse_rfc2822(s: &str) -> Result<NaiveDateTime> {
     debug!("Attempting to parse RFC 2822 format");
+    // `parse_from_rfc2822` already treats a `-0000` offset as "UTC, offset
+    // unknown" the same way chrono's own writer emits it, so no special
+    // casing is needed here beyond delegating to it.
     Ok(DateTime::parse_from_rfc2822(s)?.naive_utc())
 }
 
@@ -309,13 +476,46 @@ fn parse_time(s: &str) -> Result<NaiveDateTime> {
     Ok(local_datetime.naive_utc())
 }
 
+fn parse_relative(s: &str) -> Result<NaiveDateTime> {
+    debug!("Attempting to parse relative duration: {}", s);
+
+    let trimmed = s.trim();
+    let (is_past, magnitude) = if let Some(rest) = trimmed.strip_prefix("in ") {
+        (false, rest.trim())
+    } else if let Some(rest) = trimmed.strip_suffix("ago") {
+        (true, rest.trim())
+    } else if let Some(rest) = trimmed.strip_prefix('-') {
+        (true, rest.trim())
+    } else {
+        (false, trimmed)
+    };
+
+    let duration = ChronoDuration::from_std(humantime::parse_duration(magnitude)?)?;
+    debug!("Parsed duration: {}, is_past: {}", duration, is_past);
+
+    let now = Utc::now().naive_utc();
+    let result = if is_past {
+        now.checked_sub_signed(duration)
+    } else {
+        now.checked_add_signed(duration)
+    };
+    result.ok_or_else(|| anyhow!("Duration out of range: {}", s))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::run_workflow;
+    use crate::{parse_relative, resolve_locale, resolve_timezones, run_workflow, validate_output_format};
+    use chrono::{Duration, Utc};
     use powerpack::Item;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
     use serde::Deserialize;
+    use std::env;
+    use std::sync::Mutex;
+
+    // Guards tests that set process-wide workflow variable env vars, so they
+    // don't race with each other when run concurrently.
+    static ENV_VAR_MUTEX: Mutex<()> = Mutex::new(());
 
     #[derive(Deserialize, Debug)]
     struct TestItem {
@@ -337,13 +537,29 @@ mod tests {
     #[case("2022-09-10T10:00:00 +00:00", 1662804000)]
     #[case("2022-09-10T10:00:00 +02:00", 1662796800)]
     #[case("Sat, 10 Sep 2022 10:00:00 +0200", 1662796800)]
-    #[case("2022-09-10 10:00:00", 1662796800)]
+    #[case("2022-09-10 10:00:00+02:00", 1662796800)]
+    #[case("2022-09-10 10:00:00.123+02:00", 1662796800)]
+    #[case("2022-09-10T10:00:00.123456789Z", 1662804000)]
     #[case("2022-09-10", 1662768000)]
     fn it_parses_valid_strings(#[case] input: &str, #[case] expected_timestamp: i32) {
         let items = run_workflow(input.to_string(), None).unwrap();
         assert_item_matches(&items[0], &expected_timestamp.to_string())
     }
 
+    #[rstest]
+    fn it_parses_date_and_time_without_offset_as_local() {
+        use chrono::{NaiveDate, TimeZone};
+
+        let naive = NaiveDate::from_ymd_opt(2022, 9, 10)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let expected_timestamp = chrono::Local.from_local_datetime(&naive).unwrap().timestamp();
+
+        let items = run_workflow("2022-09-10 10:00:00".to_string(), None).unwrap();
+        assert_item_matches(&items[0], &expected_timestamp.to_string())
+    }
+
     #[rstest]
     fn it_ignores_clipboard_if_input_present() {
         let items = run_workflow(
@@ -360,6 +576,88 @@ mod tests {
         assert_item_matches(&items[0], "0")
     }
 
+    #[rstest]
+    #[case("%Y-%m-%d %H:%M:%S")]
+    #[case("%d/%m/%Y %I:%M %p")]
+    #[case("%+")]
+    fn it_accepts_valid_output_formats(#[case] pattern: &str) {
+        assert!(validate_output_format(pattern).is_ok());
+    }
+
+    #[rstest]
+    #[case("%Q")]
+    #[case("%")]
+    fn it_rejects_invalid_output_formats(#[case] pattern: &str) {
+        assert!(validate_output_format(pattern).is_err());
+    }
+
+    #[rstest]
+    #[case("America/New_York,Europe/Berlin,Asia/Tokyo", 3)]
+    #[case("America/New_York, Not/AZone, Europe/Berlin", 2)]
+    #[case("", 0)]
+    fn it_resolves_configured_timezones(#[case] raw: &str, #[case] expected_count: usize) {
+        assert_eq!(resolve_timezones(raw).len(), expected_count);
+    }
+
+    #[rstest]
+    fn it_includes_timezone_items_for_parsed_date_strings() {
+        let _guard = ENV_VAR_MUTEX.lock().unwrap();
+        env::set_var("TIMEZONES", "America/New_York");
+        let items = run_workflow("2022-09-10T10:00:00Z".to_string(), None);
+        env::remove_var("TIMEZONES");
+
+        let items = items.unwrap();
+        assert!(items.iter().any(|item| {
+            let serialised = serde_json::to_string(item).unwrap();
+            let deserialised: TestItem = serde_json::from_str(&serialised).unwrap();
+            deserialised.title == "2022-09-10 06:00:00"
+        }));
+    }
+
+    #[rstest]
+    fn it_resolves_configured_locale() {
+        assert_eq!(resolve_locale(Some("de_DE")), chrono::Locale::de_DE);
+        assert_eq!(resolve_locale(Some("not_a_locale")), chrono::Locale::POSIX);
+        assert_eq!(resolve_locale(None), chrono::Locale::POSIX);
+    }
+
+    #[rstest]
+    fn it_localizes_weekday_and_month_names_for_parsed_date_strings() {
+        let _guard = ENV_VAR_MUTEX.lock().unwrap();
+        env::set_var("OUTPUT_FORMAT", "%A %d %B %Y");
+        env::set_var("LOCALE", "de_DE");
+        let items = run_workflow("2022-09-10T10:00:00Z".to_string(), None);
+        env::remove_var("OUTPUT_FORMAT");
+        env::remove_var("LOCALE");
+
+        let items = items.unwrap();
+        assert!(items.iter().any(|item| {
+            let serialised = serde_json::to_string(item).unwrap();
+            let deserialised: TestItem = serde_json::from_str(&serialised).unwrap();
+            deserialised.title == "Samstag 10 September 2022"
+        }));
+    }
+
+    #[rstest]
+    #[case("in 2 days", 2 * 24 * 3600)]
+    #[case("3h ago", -3 * 3600)]
+    #[case("-45m", -45 * 60)]
+    #[case("1week 2days", 9 * 24 * 3600)]
+    fn it_parses_relative_durations(#[case] input: &str, #[case] expected_offset_seconds: i64) {
+        let before = Utc::now().naive_utc();
+        let parsed = parse_relative(input).unwrap();
+        let after = Utc::now().naive_utc();
+
+        let expected_min = before + Duration::seconds(expected_offset_seconds) - Duration::seconds(1);
+        let expected_max = after + Duration::seconds(expected_offset_seconds) + Duration::seconds(1);
+        assert!(parsed >= expected_min && parsed <= expected_max);
+    }
+
+    #[rstest]
+    fn it_rejects_out_of_range_relative_durations() {
+        assert!(parse_relative("in 999999999weeks").is_err());
+    }
+
     fn assert_item_matches(item: &Item, expected: &str) {
         let serialised = serde_json::to_string(item).unwrap();
         let deserialised: TestItem = serde_json::from_str(&serialised).unwrap();